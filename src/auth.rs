@@ -0,0 +1,103 @@
+use crate::{AppContext, AppError};
+use anyhow::Context;
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+const TOKEN_TTL_HOURS: i64 = 12;
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+pub struct LoginResponse {
+    token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: usize,
+}
+
+/// Error returned by [`login`]. Kept distinct from [`AppError`] so a bad username/password
+/// maps to `401 Unauthorized` instead of `AppError`'s blanket `500`, while genuine server
+/// errors (bad config, JWT signing failure) still go through `AppError`'s reporting.
+pub enum LoginError {
+    InvalidCredentials,
+    Internal(AppError),
+}
+
+impl IntoResponse for LoginError {
+    fn into_response(self) -> Response {
+        match self {
+            LoginError::InvalidCredentials => StatusCode::UNAUTHORIZED.into_response(),
+            LoginError::Internal(e) => e.into_response(),
+        }
+    }
+}
+
+impl<E> From<E> for LoginError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(err: E) -> Self {
+        Self::Internal(AppError::from(err))
+    }
+}
+
+/// `POST /login`: checks `username`/`password` against the configured admin account and,
+/// on success, issues a signed JWT the caller must then send as a `Bearer` token.
+pub async fn login(
+    State(ctx): State<AppContext>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, LoginError> {
+    if payload.username != ctx.config.auth_username {
+        return Err(LoginError::InvalidCredentials);
+    }
+    let hash = PasswordHash::new(&ctx.config.auth_password_hash).context("Invalid password hash configuration")?;
+    Argon2::default().verify_password(payload.password.as_bytes(), &hash)
+        .map_err(|_| LoginError::InvalidCredentials)?;
+    let exp = (Utc::now() + Duration::hours(TOKEN_TTL_HOURS)).timestamp() as usize;
+    let claims = Claims { sub: payload.username, exp };
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(ctx.config.jwt_secret.as_bytes()))
+        .context("Failed to sign JWT")?;
+    Ok(Json(LoginResponse { token }))
+}
+
+/// Axum middleware that rejects requests without a valid `Authorization: Bearer` token
+/// (or `token` cookie, for browser clients that can't set headers on navigation).
+pub async fn require_auth(
+    State(ctx): State<AppContext>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let token = extract_token(&request).ok_or(StatusCode::UNAUTHORIZED)?;
+    decode::<Claims>(&token, &DecodingKey::from_secret(ctx.config.jwt_secret.as_bytes()), &Validation::default())
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    Ok(next.run(request).await)
+}
+
+fn extract_token(request: &Request) -> Option<String> {
+    if let Some(token) = request.headers().get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+    {
+        return Some(token.to_string());
+    }
+    request.headers().get(header::COOKIE)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|cookies| cookies.split(';').map(str::trim).find_map(|kv| kv.strip_prefix("token=")))
+        .map(str::to_string)
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+/// A feed entry reduced to the fields we care about, independent of whether it came from
+/// an RSS 2.0 or Atom document.
+pub struct ParsedItem {
+    pub guid: String,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+}
+
+/// Parses `body` as either RSS or Atom (`feed-rs` sniffs the format) and returns its
+/// entries as format-agnostic [`ParsedItem`]s, newest first.
+pub fn parse_feed(body: &[u8]) -> Result<Vec<ParsedItem>> {
+    let feed = feed_rs::parser::parse(body).context("Feed parsing failed")?;
+    let mut items: Vec<ParsedItem> = feed.entries.into_iter().map(|entry| {
+        let link = entry.links.first().map(|l| l.href.clone());
+        let description = entry.summary.map(|t| t.content)
+            .or_else(|| entry.content.and_then(|c| c.body));
+        ParsedItem {
+            guid: entry.id,
+            title: entry.title.map(|t| t.content),
+            link,
+            description,
+            published: entry.published.or(entry.updated),
+        }
+    }).collect();
+    items.sort_by(|a, b| b.published.cmp(&a.published));
+    Ok(items)
+}
+
+/// Looks for a WebSub (PubSubHubbub) hub advertised via a `rel="hub"` link in `body`, so
+/// the caller can subscribe to push updates instead of relying solely on polling.
+pub fn discover_hub(body: &[u8]) -> Option<String> {
+    let feed = feed_rs::parser::parse(body).ok()?;
+    feed.links.iter().find(|l| l.rel.as_deref() == Some("hub")).map(|l| l.href.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS_OUT_OF_ORDER: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+<title>Test feed</title>
+<item><guid>oldest</guid><title>Oldest</title><link>https://example.com/oldest</link><pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate></item>
+<item><guid>newest</guid><title>Newest</title><link>https://example.com/newest</link><pubDate>Wed, 03 Jan 2024 00:00:00 GMT</pubDate></item>
+<item><guid>middle</guid><title>Middle</title><link>https://example.com/middle</link><pubDate>Tue, 02 Jan 2024 00:00:00 GMT</pubDate></item>
+</channel></rss>"#;
+
+    const ATOM_FEED: &str = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+<title>Test feed</title>
+<link rel="hub" href="https://hub.example.com/"/>
+<entry><id>atom-entry</id><title>Atom entry</title><updated>2024-01-01T00:00:00Z</updated></entry>
+</feed>"#;
+
+    #[test]
+    fn parse_feed_sorts_items_newest_first() {
+        let items = parse_feed(RSS_OUT_OF_ORDER.as_bytes()).unwrap();
+        let guids: Vec<&str> = items.iter().map(|i| i.guid.as_str()).collect();
+        assert_eq!(guids, vec!["newest", "middle", "oldest"]);
+    }
+
+    #[test]
+    fn parse_feed_handles_atom_documents() {
+        let items = parse_feed(ATOM_FEED.as_bytes()).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].guid, "atom-entry");
+        assert_eq!(items[0].title.as_deref(), Some("Atom entry"));
+    }
+
+    #[test]
+    fn parse_feed_rejects_garbage() {
+        assert!(parse_feed(b"not a feed").is_err());
+    }
+
+    #[test]
+    fn discover_hub_finds_rel_hub_link() {
+        assert_eq!(discover_hub(ATOM_FEED.as_bytes()).as_deref(), Some("https://hub.example.com/"));
+    }
+
+    #[test]
+    fn discover_hub_returns_none_without_a_hub_link() {
+        assert_eq!(discover_hub(RSS_OUT_OF_ORDER.as_bytes()), None);
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Result, Context};
+use anyhow::{Result, Context};
 use axum::{
     extract::{Path, State},
     http::{Method, header, StatusCode, Request, Uri},
@@ -8,7 +8,7 @@ use axum::{
 };
 use chrono::{DateTime,Utc};
 use clap::Parser;
-use mail_send::{SmtpClientBuilder, mail_builder::MessageBuilder};
+use metrics_exporter_prometheus::PrometheusHandle;
 use rust_embed::Embed;
 use serde::{Deserialize, Serialize};
 use sqlx::{
@@ -27,10 +27,18 @@ use tracing::{
     instrument, Level, Span
 };
 
+mod auth;
+mod feed;
+mod metrics;
+mod notifier;
+mod output_feed;
+mod queue;
+mod websub;
+
 static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 // Make our own error that wraps `anyhow::Error`.
-struct AppError(anyhow::Error);
+pub(crate) struct AppError(anyhow::Error);
 // TODO: use thiserror to granularize the errors and differentiate the return response.
 //  for example I don't want the SQLErrors to be sent directly if not in Debug Mode, but other more
 //  simple errors like "obj not found" need to be sent as-they-are.
@@ -69,23 +77,35 @@ struct Config {
     #[arg(long,env)]
     polling_time_sec: u64,
     #[arg(long,env)]
-    smtp_host: String,
+    pub(crate) smtp_host: String,
+    #[arg(long,env)]
+    pub(crate) smtp_port: u16,
+    #[arg(long,env)]
+    pub(crate) from_email: String,
+    #[arg(long,env)]
+    pub(crate) smtp_auth_user: String,
+    #[arg(long,env)]
+    pub(crate) smtp_auth_password: String,
     #[arg(long,env)]
-    smtp_port: u16,
+    pub(crate) auth_username: String,
     #[arg(long,env)]
-    from_email: String,
+    pub(crate) auth_password_hash: String,
     #[arg(long,env)]
-    to_email: String,
+    pub(crate) jwt_secret: String,
     #[arg(long,env)]
-    smtp_auth_user: String,
+    pub(crate) public_base_url: String,
+    /// Destination email used to backfill an `smtp` notifier for feeds created before
+    /// per-feed notifiers existed. Only consulted once, at startup; has no effect on
+    /// feeds that already have a notifier configured.
     #[arg(long,env)]
-    smtp_auth_password: String,
+    pub(crate) legacy_to_email: Option<String>,
 }
 
 #[derive(Clone)]
-struct AppContext {
-    config: Arc<Config>,
-    db: SqlitePool
+pub(crate) struct AppContext {
+    pub(crate) config: Arc<Config>,
+    pub(crate) db: SqlitePool,
+    pub(crate) metrics: PrometheusHandle,
 }
 
 #[tokio::main]
@@ -113,7 +133,9 @@ async fn main() -> Result<()> {
     // TEST - Empty last_pub_date
     // sqlx::query("UPDATE rss_feeds SET last_pub_date = NULL").execute(&db).await.unwrap();
     // Prepare Web Server Context
-    let context = AppContext {config: Arc::new(config), db: db.clone()};
+    let metrics_handle = metrics::init_metrics();
+    let context = AppContext {config: Arc::new(config), db: db.clone(), metrics: metrics_handle};
+    notifier::backfill_legacy_smtp_notifiers(&context).await.context("Legacy SMTP notifier backfill failed")?;
     // Prepare Middlewares
     let cors = CorsLayer::new()
         // allow `GET` and `POST` when accessing the resource
@@ -143,19 +165,29 @@ async fn main() -> Result<()> {
     let middlewares = ServiceBuilder::new()
         .layer(tracelayer).layer(cors);
     // Launch Web Server
-    let app = Router::new()
+    let protected_routes = Router::new()
         .route("/feeds/:id/", get(get_feed).put(modify_feed).delete(delete_feed))
         .route("/feeds/:id/forcesend", post(force_send_feed))
+        .route("/feeds/:id/notifiers/", get(list_feed_notifiers).post(create_feed_notifier))
+        .route("/feeds/:id/notifiers/:notifier_id", axum::routing::delete(delete_feed_notifier))
         .route("/feeds/", get(get_feeds).post(create_feed))
+        .route_layer(axum::middleware::from_fn_with_state(context.clone(), auth::require_auth));
+    let public_routes = Router::new()
+        .route("/login", post(auth::login))
+        .route("/feed.xml", get(get_aggregated_feed))
+        .route("/metrics", get(metrics_handler))
+        .route("/websub/:feed_id", get(websub::verify_subscription).post(websub::receive_update))
         .route("/", get(index_handler))
         .route("/index.html", get(index_handler))
-        .route("/*file", get(static_handler))
+        .route("/*file", get(static_handler));
+    let app = protected_routes.merge(public_routes)
         .layer(middlewares)
         .with_state(context.clone());  // TODO: AXUM LOG REQUESTS
     let context2 = context.clone();
     tokio::spawn(async move {
         send_feeds_scheduler(&context2).await;
     });
+    tokio::spawn(queue::worker_loop(context.clone()));
     // run our app with hyper, listening globally on port 3000
     let listener = tokio::net::TcpListener::bind((context.config.http_host.as_str(), context.config.http_port))
         .await.context(format!("Failed to bind Web Service on {}:{}", context.config.http_host, context.config.http_port))?;
@@ -167,6 +199,7 @@ async fn main() -> Result<()> {
 #[instrument(skip_all)]
 async fn send_feeds_scheduler(ctx: &AppContext) {
     loop {
+        ::metrics::counter!("rss_notifier_polls_total").increment(1);
         if let Err(e) = send_feeds(ctx).await {
             error!("{}", e);
         }
@@ -177,8 +210,20 @@ async fn send_feeds_scheduler(ctx: &AppContext) {
 async fn send_feeds(ctx: &AppContext) -> Result<()> {
     let feeds: Vec<RssFeed> = sqlx::query_as("SELECT id, name, feed_url, last_pub_date FROM rss_feeds ORDER BY id")
         .fetch_all(&ctx.db).await?;
+    // Feeds with an active WebSub subscription get pushed updates instead; polling them
+    // too would just duplicate the hub's own delivery.
+    let websub_fed: std::collections::HashSet<u32> = sqlx::query_scalar(
+        "SELECT feed_id FROM websub_subscriptions WHERE lease_expires_at > $1")
+        .bind(Utc::now())
+        .fetch_all(&ctx.db).await?
+        .into_iter().collect();
     for feed in feeds.into_iter() {
+        if websub_fed.contains(&feed.id) {
+            debug!("Skipping poll for feed {}, served via WebSub", feed.id);
+            continue;
+        }
         info!("Spawn send_feed number {}", feed.id);
+        ::metrics::counter!("rss_notifier_feeds_checked_total").increment(1);
         let ctx2 = ctx.clone();
         tokio::spawn(async move {
             check_send_feed(&ctx2, feed).await;
@@ -187,29 +232,23 @@ async fn send_feeds(ctx: &AppContext) -> Result<()> {
     Ok(())
 }
 
-#[instrument(skip_all, fields(id = feed.id))]
-async fn check_send_feed(ctx: &AppContext, feed: RssFeed) {
+#[instrument(skip_all, fields(id = rss_feed.id))]
+async fn check_send_feed(ctx: &AppContext, rss_feed: RssFeed) {
     let try_block = async move {
-        let body = reqwest::get(feed.feed_url.clone()).await.context("RSS Fetch failed")?.bytes().await?;
-        let channel = rss::Channel::read_from(&body[..]).context("RSS channel read failed")?;
-        let item = channel.items.into_iter().next().ok_or(anyhow!("RSS channel item empty"))?;
-        let pub_date = DateTime::parse_from_rfc2822(item.pub_date.as_ref()
-            .ok_or(anyhow!("RSS Item missing pub_date"))?).context("Failed RFC2822 RSS pub_date parsing")?;
-        let link = item.link.as_ref().ok_or(anyhow!("RSS Item missing link"))?;
-        debug!("PubDate: {} and Link: {}", pub_date, link);
-        match feed.last_pub_date {
-            Some(last_pub_date) if last_pub_date == pub_date => {
-                // Do nothing
-                Result::<()>::Ok(())
-            },
-            _ => {
-                send_notification(ctx, &feed, &item).await?;
-                // Update the db
-                sqlx::query("UPDATE rss_feeds SET last_pub_date = $1 WHERE id = $2")
-                    .bind(pub_date).bind(feed.id)
-                    .execute(&ctx.db).await.context("Failed to set last_pub_date in DB").map(|_| {})
-            }
+        let fetch_started = std::time::Instant::now();
+        let fetch_result = reqwest::get(rss_feed.feed_url.clone()).await.context("RSS Fetch failed");
+        // Deliberately unlabeled: a per-feed_id label would create one permanent time
+        // series per feed ever created, including deleted ones. The tracing span on this
+        // function already carries the feed id for anyone who needs to dig into a specific
+        // feed's fetches.
+        ::metrics::histogram!("rss_notifier_feed_fetch_duration_seconds")
+            .record(fetch_started.elapsed().as_secs_f64());
+        if fetch_result.is_err() {
+            ::metrics::counter!("rss_notifier_feed_fetch_failures_total").increment(1);
         }
+        let body = fetch_result?.bytes().await?;
+        let items = feed::parse_feed(&body).context("Failed to parse feed")?;
+        process_new_items(ctx, &rss_feed, &items).await
     };
     if let Err(e) = try_block.await {
         if enabled!(Level::DEBUG) {
@@ -220,26 +259,58 @@ async fn check_send_feed(ctx: &AppContext, feed: RssFeed) {
     }
 }
 
-async fn send_notification(ctx: &AppContext, feed: &RssFeed, rssitem: &rss::Item) -> Result<()> {
-    // Build a simple multipart message
-    let link = rssitem.link.as_ref().ok_or(anyhow!("RSS Item missing link"))?;
-    info!("Sending Mail Notification for feed {}", feed.id);
-    let title = rssitem.title.as_ref().ok_or(anyhow!("RSS Item missing title"))?;
-    let description = rssitem.description.as_ref().map_or("", |x| x.as_str());
-    let from_name = format!("RSS {}", feed.name);
-    let html_body = format!("<p>Original Post: <a href=\"{}\">{}</a></p>{}", link, title, description);
-    let text_body = format!("Original Post: {} - {}\r\n", title, link);
-    let message = MessageBuilder::new()
-        .from((from_name.as_str(), ctx.config.from_email.as_str()))
-        .to(ctx.config.to_email.as_str())
-        .subject(title)
-        .html_body(html_body)
-        .text_body(text_body);
-    SmtpClientBuilder::new(ctx.config.smtp_host.as_str(), ctx.config.smtp_port)
-        .implicit_tls(false)
-        .credentials((ctx.config.smtp_auth_user.as_str(), ctx.config.smtp_auth_password.as_str()))
-        .connect().await.context("Error connecting to SMTP Server")?
-        .send(message).await.context("Error sending message to SMTP Server")
+/// Given newly fetched `items` for `rss_feed`, enqueues a send job for every item that's
+/// new since the last check and advances the feed's dedup state accordingly. Shared by
+/// the polling path and the WebSub push callback, which both end up with a batch of
+/// parsed items to run through the same new-item detection.
+///
+/// The very first observation of a feed (no `last_pub_date` and no recorded `seen_items`)
+/// only establishes that baseline instead of notifying for every item already in the
+/// document, so a feed with a long backlog doesn't fire a notification per historical item.
+pub(crate) async fn process_new_items(ctx: &AppContext, rss_feed: &RssFeed, items: &[feed::ParsedItem]) -> Result<()> {
+    let seen_guids: std::collections::HashSet<String> = sqlx::query_scalar(
+        "SELECT item_guid FROM seen_items WHERE feed_id = $1")
+        .bind(rss_feed.id)
+        .fetch_all(&ctx.db).await.context("Failed to load seen items")?
+        .into_iter().collect();
+    let first_poll = rss_feed.last_pub_date.is_none() && seen_guids.is_empty();
+    let mut max_pub_date = rss_feed.last_pub_date;
+    for item in items {
+        let is_new = !first_poll && match item.published {
+            Some(published) => rss_feed.last_pub_date.map_or(true, |last| published > last),
+            None => !seen_guids.contains(&item.guid),
+        };
+        if is_new {
+            debug!("New item: guid={} link={:?}", item.guid, item.link);
+            let notify_item = notifier::NotificationItem {
+                guid: item.guid.clone(),
+                title: item.title.clone(),
+                link: item.link.clone(),
+                description: item.description.clone(),
+                published: item.published,
+            };
+            queue::enqueue_send_job(ctx, rss_feed.id, &notify_item).await?;
+        }
+        // Always advance the baseline for the item, whether or not it was new: for
+        // already-known items this is a no-op (max() / INSERT OR IGNORE), and on the
+        // first poll it's how we record the starting point without notifying.
+        match item.published {
+            Some(published) => {
+                max_pub_date = Some(max_pub_date.map_or(published, |max| max.max(published)));
+            }
+            None => {
+                sqlx::query("INSERT OR IGNORE INTO seen_items (feed_id, item_guid, seen_at) VALUES ($1, $2, $3)")
+                    .bind(rss_feed.id).bind(&item.guid).bind(Utc::now())
+                    .execute(&ctx.db).await.context("Failed to record seen item")?;
+            }
+        }
+    }
+    if max_pub_date != rss_feed.last_pub_date {
+        sqlx::query("UPDATE rss_feeds SET last_pub_date = $1 WHERE id = $2")
+            .bind(max_pub_date).bind(rss_feed.id)
+            .execute(&ctx.db).await.context("Failed to set last_pub_date in DB")?;
+    }
+    Ok(())
 }
 
 #[derive(Deserialize)]
@@ -249,11 +320,11 @@ struct CreateRssFeed {
 }
 
 #[derive(Serialize,FromRow)]
-struct RssFeed {
-    id: u32,
-    name: String,
-    feed_url: String,
-    last_pub_date: Option<DateTime<Utc>>,
+pub(crate) struct RssFeed {
+    pub(crate) id: u32,
+    pub(crate) name: String,
+    pub(crate) feed_url: String,
+    pub(crate) last_pub_date: Option<DateTime<Utc>>,
 }
 
 // TODO: Better errors for all the endpoints
@@ -274,6 +345,18 @@ async fn create_feed(
         feed_url:payload.feed_url,
         last_pub_date:None,
     };
+    let ctx2 = ctx.clone();
+    let feed_for_websub = RssFeed {
+        id: result.id,
+        name: result.name.clone(),
+        feed_url: result.feed_url.clone(),
+        last_pub_date: result.last_pub_date,
+    };
+    tokio::spawn(async move {
+        if let Err(e) = websub::discover_and_subscribe(&ctx2, &feed_for_websub).await {
+            error!("WebSub discovery failed for feed {}: {:#}", feed_for_websub.id, e);
+        }
+    });
     Ok((StatusCode::CREATED, Json(result)))
 }
 
@@ -282,9 +365,38 @@ async fn modify_feed(
     Path(feed_id): Path<u32>,
     Json(payload): Json<CreateRssFeed>,
 ) -> Result<(StatusCode, Json<RssFeed>), AppError> {
-    sqlx::query("UPDATE rss_feeds SET name = $1, feed_url = $2 WHERE id = $3")
-        .bind(&payload.name).bind(&payload.feed_url).bind(feed_id)
-        .execute(&ctx.db).await?;
+    let previous_feed_url: Option<String> = sqlx::query_scalar("SELECT feed_url FROM rss_feeds WHERE id = $1")
+        .bind(feed_id)
+        .fetch_optional(&ctx.db).await?;
+    let url_changed = previous_feed_url.is_some_and(|url| url != payload.feed_url);
+    if url_changed {
+        // The new URL is a different feed as far as dedup is concerned: a stale
+        // last_pub_date/seen_items baseline from the old URL would either spam (baseline
+        // older than the new feed's items) or silently suppress (baseline newer) every
+        // notification until the new feed's own items catch up to it.
+        sqlx::query("UPDATE rss_feeds SET name = $1, feed_url = $2, last_pub_date = NULL WHERE id = $3")
+            .bind(&payload.name).bind(&payload.feed_url).bind(feed_id)
+            .execute(&ctx.db).await?;
+        sqlx::query("DELETE FROM seen_items WHERE feed_id = $1")
+            .bind(feed_id)
+            .execute(&ctx.db).await?;
+        // The old subscription's topic is stale too: drop it immediately so send_feeds
+        // stops excluding this feed from polling, then try to subscribe the new URL's hub.
+        sqlx::query("DELETE FROM websub_subscriptions WHERE feed_id = $1")
+            .bind(feed_id)
+            .execute(&ctx.db).await?;
+        let ctx2 = ctx.clone();
+        let feed_for_websub = RssFeed { id: feed_id, name: payload.name.clone(), feed_url: payload.feed_url.clone(), last_pub_date: None };
+        tokio::spawn(async move {
+            if let Err(e) = websub::discover_and_subscribe(&ctx2, &feed_for_websub).await {
+                error!("WebSub discovery failed for feed {}: {:#}", feed_for_websub.id, e);
+            }
+        });
+    } else {
+        sqlx::query("UPDATE rss_feeds SET name = $1, feed_url = $2 WHERE id = $3")
+            .bind(&payload.name).bind(&payload.feed_url).bind(feed_id)
+            .execute(&ctx.db).await?;
+    }
     get_feed(State(ctx), Path(feed_id)).await
 }
 
@@ -329,6 +441,53 @@ async fn force_send_feed(
     Ok(StatusCode::OK)
 }
 
+async fn list_feed_notifiers(
+    State(ctx): State<AppContext>,
+    Path(feed_id): Path<u32>,
+) -> Result<(StatusCode, Json<Vec<notifier::FeedNotifierRow>>), AppError> {
+    let notifiers: Vec<notifier::FeedNotifierRow> = sqlx::query_as(
+        "SELECT id, feed_id, kind, settings FROM feed_notifiers WHERE feed_id = $1 ORDER BY id")
+        .bind(feed_id)
+        .fetch_all(&ctx.db).await?;
+    Ok((StatusCode::OK, Json(notifiers)))
+}
+
+async fn create_feed_notifier(
+    State(ctx): State<AppContext>,
+    Path(feed_id): Path<u32>,
+    Json(payload): Json<notifier::CreateFeedNotifier>,
+) -> Result<(StatusCode, Json<notifier::FeedNotifierRow>), AppError> {
+    notifier::validate_notifier_config(&payload.kind, &payload.settings)?;
+    let settings = payload.settings.to_string();
+    let mut transaction = ctx.db.begin().await?;
+    sqlx::query("INSERT INTO feed_notifiers (feed_id, kind, settings) VALUES ($1, $2, $3)")
+        .bind(feed_id).bind(&payload.kind).bind(&settings)
+        .execute(&mut *transaction).await?;
+    let (id,): (u32,) = sqlx::query_as("SELECT last_insert_rowid()").fetch_one(&mut *transaction).await?;
+    transaction.commit().await?;
+    let result = notifier::FeedNotifierRow { id, feed_id, kind: payload.kind, settings };
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+async fn delete_feed_notifier(
+    State(ctx): State<AppContext>,
+    Path((feed_id, notifier_id)): Path<(u32, u32)>,
+) -> Result<StatusCode, AppError> {
+    sqlx::query("DELETE FROM feed_notifiers WHERE id = $1 AND feed_id = $2")
+        .bind(notifier_id).bind(feed_id)
+        .execute(&ctx.db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_aggregated_feed(State(ctx): State<AppContext>) -> Result<Response, AppError> {
+    let xml = output_feed::generate_feed_xml(&ctx).await?;
+    Ok(([(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")], xml).into_response())
+}
+
+async fn metrics_handler(State(ctx): State<AppContext>) -> String {
+    ctx.metrics.render()
+}
+
 // Fallback Route
 fn not_found_body() -> Html<&'static str> {
     Html("<h1>404</h1><p>Not Found</p>")
@@ -368,3 +527,104 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::OnceLock;
+
+    fn test_metrics_handle() -> PrometheusHandle {
+        static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+        HANDLE.get_or_init(metrics::init_metrics).clone()
+    }
+
+    async fn test_context() -> AppContext {
+        let db = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        MIGRATOR.run(&db).await.unwrap();
+        let config = Config {
+            database_url: "sqlite::memory:".into(),
+            http_host: "127.0.0.1".into(),
+            http_port: 0,
+            polling_time_sec: 60,
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            from_email: "test@example.com".into(),
+            smtp_auth_user: "user".into(),
+            smtp_auth_password: "pass".into(),
+            auth_username: "admin".into(),
+            auth_password_hash: "".into(),
+            jwt_secret: "secret".into(),
+            public_base_url: "http://localhost".into(),
+            legacy_to_email: None,
+        };
+        AppContext { config: Arc::new(config), db, metrics: test_metrics_handle() }
+    }
+
+    async fn insert_feed(ctx: &AppContext, feed_url: &str) -> RssFeed {
+        sqlx::query("INSERT INTO rss_feeds (name, feed_url) VALUES ('Test feed', $1)")
+            .bind(feed_url)
+            .execute(&ctx.db).await.unwrap();
+        let (id,): (u32,) = sqlx::query_as("SELECT last_insert_rowid()").fetch_one(&ctx.db).await.unwrap();
+        RssFeed { id, name: "Test feed".into(), feed_url: feed_url.into(), last_pub_date: None }
+    }
+
+    fn item(guid: &str, published: Option<DateTime<Utc>>) -> feed::ParsedItem {
+        feed::ParsedItem {
+            guid: guid.into(),
+            title: Some(guid.into()),
+            link: Some(format!("https://example.com/{}", guid)),
+            description: None,
+            published,
+        }
+    }
+
+    async fn job_count(ctx: &AppContext) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM jobs").fetch_one(&ctx.db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn first_poll_baselines_dated_items_without_notifying() {
+        let ctx = test_context().await;
+        let feed = insert_feed(&ctx, "https://example.com/feed.xml").await;
+        let old = Utc::now() - chrono::Duration::days(2);
+        let newer = Utc::now() - chrono::Duration::days(1);
+        let items = vec![item("a", Some(old)), item("b", Some(newer))];
+
+        process_new_items(&ctx, &feed, &items).await.unwrap();
+
+        assert_eq!(job_count(&ctx).await, 0);
+        let last_pub_date: Option<DateTime<Utc>> = sqlx::query_scalar("SELECT last_pub_date FROM rss_feeds WHERE id = $1")
+            .bind(feed.id).fetch_one(&ctx.db).await.unwrap();
+        assert_eq!(last_pub_date, Some(newer));
+    }
+
+    #[tokio::test]
+    async fn first_poll_baselines_dateless_items_without_notifying() {
+        let ctx = test_context().await;
+        let feed = insert_feed(&ctx, "https://example.com/feed.xml").await;
+        let items = vec![item("a", None), item("b", None)];
+
+        process_new_items(&ctx, &feed, &items).await.unwrap();
+
+        assert_eq!(job_count(&ctx).await, 0);
+        let seen: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM seen_items WHERE feed_id = $1")
+            .bind(feed.id).fetch_one(&ctx.db).await.unwrap();
+        assert_eq!(seen, 2);
+    }
+
+    #[tokio::test]
+    async fn subsequent_poll_only_notifies_items_newer_than_the_baseline() {
+        let ctx = test_context().await;
+        let mut feed = insert_feed(&ctx, "https://example.com/feed.xml").await;
+        sqlx::query("INSERT INTO feed_notifiers (feed_id, kind, settings) VALUES ($1, 'webhook', '{\"url\":\"https://example.com/hook\"}')")
+            .bind(feed.id).execute(&ctx.db).await.unwrap();
+        let baseline = Utc::now() - chrono::Duration::days(1);
+        process_new_items(&ctx, &feed, &[item("a", Some(baseline))]).await.unwrap();
+        feed.last_pub_date = Some(baseline);
+
+        let newer = Utc::now();
+        process_new_items(&ctx, &feed, &[item("a", Some(baseline)), item("b", Some(newer))]).await.unwrap();
+
+        assert_eq!(job_count(&ctx).await, 1);
+    }
+}
@@ -0,0 +1,10 @@
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+/// Installs the global Prometheus recorder and returns the handle used to render the
+/// `/metrics` response. Must be called once, before any `metrics::counter!`/`histogram!`
+/// call elsewhere in the app.
+pub fn init_metrics() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("Failed to install Prometheus recorder")
+}
@@ -0,0 +1,241 @@
+use crate::{AppContext, RssFeed};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mail_send::{mail_builder::MessageBuilder, SmtpClientBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use tracing::info;
+
+/// A feed item reduced to the fields a notifier needs, independent of the feed format
+/// (RSS/Atom) it was parsed from. `guid` is the entry id reported by the feed parser
+/// (see `feed::parse_feed`), used as the per-item identifier for dedup and retry bookkeeping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationItem {
+    pub guid: String,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+}
+
+/// A destination a feed's new items are forwarded to.
+///
+/// Implementations receive the already-parsed feed item and are responsible for
+/// formatting and delivering it however fits the channel (email, chat message, webhook call...).
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()>;
+}
+
+#[derive(Deserialize)]
+struct SmtpSettings {
+    to_email: String,
+}
+
+struct SmtpNotifier {
+    to_email: String,
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn notify(&self, ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()> {
+        let link = item.link.as_ref().ok_or(anyhow!("RSS Item missing link"))?;
+        let title = item.title.as_ref().ok_or(anyhow!("RSS Item missing title"))?;
+        let description = item.description.as_ref().map_or("", |x| x.as_str());
+        let from_name = format!("RSS {}", feed.name);
+        let html_body = format!("<p>Original Post: <a href=\"{}\">{}</a></p>{}", link, title, description);
+        let text_body = format!("Original Post: {} - {}\r\n", title, link);
+        let message = MessageBuilder::new()
+            .from((from_name.as_str(), ctx.config.from_email.as_str()))
+            .to(self.to_email.as_str())
+            .subject(title)
+            .html_body(html_body)
+            .text_body(text_body);
+        SmtpClientBuilder::new(ctx.config.smtp_host.as_str(), ctx.config.smtp_port)
+            .implicit_tls(false)
+            .credentials((ctx.config.smtp_auth_user.as_str(), ctx.config.smtp_auth_password.as_str()))
+            .connect().await.context("Error connecting to SMTP Server")?
+            .send(message).await.context("Error sending message to SMTP Server")
+    }
+}
+
+#[derive(Deserialize)]
+struct TelegramSettings {
+    bot_token: String,
+    chat_id: String,
+}
+
+struct TelegramNotifier {
+    bot_token: String,
+    chat_id: String,
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn notify(&self, _ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()> {
+        let link = item.link.as_ref().ok_or(anyhow!("RSS Item missing link"))?;
+        let title = item.title.as_ref().ok_or(anyhow!("RSS Item missing title"))?;
+        let text = format!("{}: {}\n{}", feed.name, title, link);
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let resp = reqwest::Client::new()
+            .post(url)
+            .json(&serde_json::json!({"chat_id": self.chat_id, "text": text}))
+            .send().await.context("Error calling Telegram API")?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Telegram API returned {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct DiscordSettings {
+    webhook_url: String,
+}
+
+struct DiscordNotifier {
+    webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify(&self, _ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()> {
+        let link = item.link.as_ref().ok_or(anyhow!("RSS Item missing link"))?;
+        let title = item.title.as_ref().ok_or(anyhow!("RSS Item missing title"))?;
+        let content = format!("**{}**: {}\n{}", feed.name, title, link);
+        let resp = reqwest::Client::new()
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({"content": content}))
+            .send().await.context("Error calling Discord webhook")?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Discord webhook returned {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct WebhookSettings {
+    url: String,
+}
+
+struct WebhookNotifier {
+    url: String,
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, _ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()> {
+        let resp = reqwest::Client::new()
+            .post(&self.url)
+            .json(&serde_json::json!({
+                "feed_id": feed.id,
+                "feed_name": feed.name,
+                "title": item.title,
+                "link": item.link,
+                "description": item.description,
+            }))
+            .send().await.context("Error calling generic webhook")?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Webhook returned {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Row stored in `feed_notifiers`: which channel (`kind`) a feed notifies through, and its
+/// channel-specific configuration serialized as JSON in `settings`.
+#[derive(Serialize, FromRow)]
+pub struct FeedNotifierRow {
+    pub id: u32,
+    pub feed_id: u32,
+    pub kind: String,
+    pub settings: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreateFeedNotifier {
+    pub kind: String,
+    pub settings: Value,
+}
+
+pub(crate) fn build_notifier(kind: &str, settings: &str) -> Result<Box<dyn Notifier>> {
+    match kind {
+        "smtp" => {
+            let settings: SmtpSettings = serde_json::from_str(settings).context("Invalid smtp notifier settings")?;
+            Ok(Box::new(SmtpNotifier { to_email: settings.to_email }))
+        }
+        "telegram" => {
+            let settings: TelegramSettings = serde_json::from_str(settings).context("Invalid telegram notifier settings")?;
+            Ok(Box::new(TelegramNotifier { bot_token: settings.bot_token, chat_id: settings.chat_id }))
+        }
+        "discord" => {
+            let settings: DiscordSettings = serde_json::from_str(settings).context("Invalid discord notifier settings")?;
+            Ok(Box::new(DiscordNotifier { webhook_url: settings.webhook_url }))
+        }
+        "webhook" => {
+            let settings: WebhookSettings = serde_json::from_str(settings).context("Invalid webhook notifier settings")?;
+            Ok(Box::new(WebhookNotifier { url: settings.url }))
+        }
+        other => Err(anyhow!("Unknown notifier kind '{}'", other)),
+    }
+}
+
+/// Validates that `kind`/`settings` describe a notifier we know how to build, without
+/// actually dispatching anything. Used to reject bad configuration at creation time.
+pub fn validate_notifier_config(kind: &str, settings: &Value) -> Result<()> {
+    build_notifier(kind, &settings.to_string()).map(|_| ())
+}
+
+/// Loads every notifier configured for `feed`. Used by the queue to fan a new item out
+/// into one job per notifier, so each channel's delivery is retried/dead-lettered
+/// independently of the others.
+pub(crate) async fn load_feed_notifiers(ctx: &AppContext, feed_id: u32) -> Result<Vec<FeedNotifierRow>> {
+    sqlx::query_as("SELECT id, feed_id, kind, settings FROM feed_notifiers WHERE feed_id = $1")
+        .bind(feed_id)
+        .fetch_all(&ctx.db).await.context("Failed to load feed notifiers")
+}
+
+/// Builds and runs the single notifier described by `row`, recording the usual
+/// sent/failed metrics labeled by its `kind`.
+pub(crate) async fn notify_via(ctx: &AppContext, feed: &RssFeed, row: &FeedNotifierRow, item: &NotificationItem) -> Result<()> {
+    let result = match build_notifier(&row.kind, &row.settings) {
+        Ok(notifier) => notifier.notify(ctx, feed, item).await,
+        Err(e) => Err(e),
+    };
+    match &result {
+        Ok(()) => {
+            ::metrics::counter!("rss_notifier_notifications_sent_total", "kind" => row.kind.clone()).increment(1);
+        }
+        Err(_) => {
+            ::metrics::counter!("rss_notifier_notifications_failed_total", "kind" => row.kind.clone()).increment(1);
+        }
+    }
+    result
+}
+
+/// One-time startup migration for feeds created before per-feed notifiers existed: gives
+/// each feed that still has zero configured notifiers an `smtp` notifier pointed at
+/// `legacy_to_email`, so upgrading doesn't silently stop delivering to feeds that relied
+/// on the old global `to_email` setting. No-ops if `legacy_to_email` isn't set.
+pub async fn backfill_legacy_smtp_notifiers(ctx: &AppContext) -> Result<()> {
+    let Some(to_email) = ctx.config.legacy_to_email.clone() else {
+        return Ok(());
+    };
+    let feed_ids: Vec<u32> = sqlx::query_scalar(
+        "SELECT rf.id FROM rss_feeds rf LEFT JOIN feed_notifiers fn ON fn.feed_id = rf.id WHERE fn.id IS NULL")
+        .fetch_all(&ctx.db).await.context("Failed to list feeds without notifiers")?;
+    if feed_ids.is_empty() {
+        return Ok(());
+    }
+    let settings = serde_json::json!({"to_email": to_email}).to_string();
+    for feed_id in feed_ids {
+        info!("Backfilling legacy SMTP notifier for feed {}", feed_id);
+        sqlx::query("INSERT INTO feed_notifiers (feed_id, kind, settings) VALUES ($1, 'smtp', $2)")
+            .bind(feed_id).bind(&settings)
+            .execute(&ctx.db).await.context("Failed to backfill smtp notifier")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::AppContext;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+const MAX_ENTRIES: i64 = 100;
+
+#[derive(FromRow)]
+struct NotifiedItemRow {
+    id: u32,
+    feed_name: String,
+    title: Option<String>,
+    link: Option<String>,
+    pub_date: Option<DateTime<Utc>>,
+    notified_at: DateTime<Utc>,
+}
+
+/// Builds a combined Atom feed out of the most recently notified items across all
+/// tracked feeds, so the aggregate can be subscribed to directly instead of (or
+/// alongside) per-feed notifications.
+pub async fn generate_feed_xml(ctx: &AppContext) -> Result<String> {
+    let rows: Vec<NotifiedItemRow> = sqlx::query_as(
+        "SELECT id, feed_name, title, link, pub_date, notified_at FROM notified_items \
+         ORDER BY notified_at DESC LIMIT $1")
+        .bind(MAX_ENTRIES)
+        .fetch_all(&ctx.db).await.context("Failed to load notified items")?;
+    let updated = rows.first().map_or_else(Utc::now, |r| r.notified_at);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("  <title>rss-notifier aggregate</title>\n");
+    xml.push_str("  <id>urn:rss-notifier:feed.xml</id>\n");
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    for row in &rows {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>urn:rss-notifier:notified-item:{}</id>\n", row.id));
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(row.title.as_deref().unwrap_or(""))));
+        if let Some(link) = &row.link {
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(link)));
+        }
+        xml.push_str(&format!("    <author><name>{}</name></author>\n", escape_xml(&row.feed_name)));
+        let published = row.pub_date.unwrap_or(row.notified_at);
+        xml.push_str(&format!("    <published>{}</published>\n", published.to_rfc3339()));
+        xml.push_str(&format!("    <updated>{}</updated>\n", row.notified_at.to_rfc3339()));
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    Ok(xml)
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
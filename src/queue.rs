@@ -0,0 +1,123 @@
+use crate::notifier::{self, NotificationItem};
+use crate::{AppContext, RssFeed};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+use tracing::{error, info, instrument, warn};
+
+const BASE_BACKOFF_SECS: i64 = 30;
+const MAX_BACKOFF_SECS: i64 = 3600;
+const MAX_ATTEMPTS: i32 = 8;
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[derive(FromRow)]
+struct JobRow {
+    id: u32,
+    feed_id: u32,
+    notifier_id: u32,
+    payload: String,
+    attempts: i32,
+}
+
+/// Persists one send job per notifier configured for `feed_id`, so each channel's
+/// delivery survives a process restart and is retried/dead-lettered independently of the
+/// others. Warns (without enqueuing anything) if the feed has no configured notifiers.
+pub async fn enqueue_send_job(ctx: &AppContext, feed_id: u32, item: &NotificationItem) -> Result<()> {
+    let notifiers = notifier::load_feed_notifiers(ctx, feed_id).await?;
+    if notifiers.is_empty() {
+        warn!("Feed {} has no configured notifiers; item {} will not be delivered anywhere", feed_id, item.guid);
+        return Ok(());
+    }
+    let payload = serde_json::to_string(item).context("Failed to serialize notification payload")?;
+    for row in &notifiers {
+        sqlx::query("INSERT INTO jobs (feed_id, notifier_id, item_guid, payload, attempts, next_attempt_at, state)\
+        VALUES ($1, $2, $3, $4, 0, $5, 'pending')")
+            .bind(feed_id).bind(row.id).bind(&item.guid).bind(&payload).bind(Utc::now())
+            .execute(&ctx.db).await.context("Failed to enqueue send job")?;
+    }
+    Ok(())
+}
+
+/// Background worker that repeatedly claims due jobs and attempts delivery, rescheduling
+/// failures with exponential backoff until `MAX_ATTEMPTS` is reached.
+#[instrument(skip_all)]
+pub async fn worker_loop(ctx: AppContext) {
+    loop {
+        if let Err(e) = run_due_jobs(&ctx).await {
+            error!("{:#}", e);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn run_due_jobs(ctx: &AppContext) -> Result<()> {
+    let jobs: Vec<JobRow> = sqlx::query_as(
+        "SELECT id, feed_id, notifier_id, payload, attempts FROM jobs WHERE state = 'pending' AND next_attempt_at <= $1 ORDER BY id")
+        .bind(Utc::now())
+        .fetch_all(&ctx.db).await.context("Failed to load due jobs")?;
+    for job in jobs {
+        run_job(ctx, job).await;
+    }
+    Ok(())
+}
+
+/// Records that `item` was delivered for `feed`, for the aggregated output feed.
+/// Several per-notifier jobs can deliver the same logical item, so this ignores the
+/// insert when a row for `(feed_id, item_guid)` already exists instead of duplicating it.
+async fn record_notified_item(ctx: &AppContext, feed: &RssFeed, item: &NotificationItem) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO notified_items (feed_id, feed_name, title, link, pub_date, notified_at, item_guid)\
+    VALUES ($1, $2, $3, $4, $5, $6, $7)")
+        .bind(feed.id).bind(&feed.name).bind(&item.title).bind(&item.link).bind(item.published).bind(Utc::now()).bind(&item.guid)
+        .execute(&ctx.db).await.context("Failed to insert notified item")?;
+    Ok(())
+}
+
+#[instrument(skip_all, fields(id = job.id))]
+async fn run_job(ctx: &AppContext, job: JobRow) {
+    let feed: Option<RssFeed> = sqlx::query_as("SELECT id, name, feed_url, last_pub_date FROM rss_feeds WHERE id = $1")
+        .bind(job.feed_id).fetch_optional(&ctx.db).await.unwrap_or(None);
+    let Some(feed) = feed else {
+        // The feed was deleted since this job was enqueued: nothing left to notify.
+        let _ = sqlx::query("DELETE FROM jobs WHERE id = $1").bind(job.id).execute(&ctx.db).await;
+        return;
+    };
+    let notifier_row: Option<notifier::FeedNotifierRow> = sqlx::query_as(
+        "SELECT id, feed_id, kind, settings FROM feed_notifiers WHERE id = $1")
+        .bind(job.notifier_id).fetch_optional(&ctx.db).await.unwrap_or(None);
+    let Some(notifier_row) = notifier_row else {
+        // The notifier was deleted since this job was enqueued: nothing left to deliver to.
+        let _ = sqlx::query("DELETE FROM jobs WHERE id = $1").bind(job.id).execute(&ctx.db).await;
+        return;
+    };
+    let item: NotificationItem = match serde_json::from_str(&job.payload) {
+        Ok(item) => item,
+        Err(e) => {
+            error!("Job has invalid payload, moving to dead letter: {:#}", e);
+            let _ = sqlx::query("UPDATE jobs SET state = 'dead' WHERE id = $1").bind(job.id).execute(&ctx.db).await;
+            return;
+        }
+    };
+    match notifier::notify_via(ctx, &feed, &notifier_row, &item).await {
+        Ok(()) => {
+            info!("Job delivered");
+            if let Err(e) = record_notified_item(ctx, &feed, &item).await {
+                error!("Failed to record notified item: {:#}", e);
+            }
+            let _ = sqlx::query("DELETE FROM jobs WHERE id = $1").bind(job.id).execute(&ctx.db).await;
+        }
+        Err(e) => {
+            let attempts = job.attempts + 1;
+            if attempts >= MAX_ATTEMPTS {
+                error!("Notifier {} (kind={}) exhausted retries, moving to dead letter: {:#}", notifier_row.id, notifier_row.kind, e);
+                let _ = sqlx::query("UPDATE jobs SET attempts = $1, state = 'dead' WHERE id = $2")
+                    .bind(attempts).bind(job.id).execute(&ctx.db).await;
+            } else {
+                let backoff = (BASE_BACKOFF_SECS * 2i64.pow(attempts as u32)).min(MAX_BACKOFF_SECS);
+                let next_attempt_at: DateTime<Utc> = Utc::now() + Duration::seconds(backoff);
+                error!("Notifier {} (kind={}) failed (attempt {}), retrying at {}: {:#}", notifier_row.id, notifier_row.kind, attempts, next_attempt_at, e);
+                let _ = sqlx::query("UPDATE jobs SET attempts = $1, next_attempt_at = $2 WHERE id = $3")
+                    .bind(attempts).bind(next_attempt_at).bind(job.id).execute(&ctx.db).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{feed, process_new_items, AppContext, RssFeed};
+use anyhow::{anyhow, Context, Result};
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::Deserialize;
+use sha2::Sha256;
+use sqlx::FromRow;
+use tracing::error;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const LEASE_SECONDS: i64 = 10 * 24 * 60 * 60;
+
+#[derive(FromRow)]
+struct WebSubSubscription {
+    hub_url: String,
+    secret: String,
+}
+
+/// Fetches `feed.feed_url` looking for a `rel="hub"` link; if found, subscribes to push
+/// updates from that hub so the feed can be taken off the polling schedule. Feeds
+/// without a hub are left untouched and keep being polled as before.
+pub async fn discover_and_subscribe(ctx: &AppContext, feed_row: &RssFeed) -> Result<()> {
+    let body = reqwest::get(feed_row.feed_url.clone()).await.context("Failed to fetch feed for WebSub discovery")?
+        .bytes().await.context("Failed to read feed body")?;
+    let Some(hub_url) = feed::discover_hub(&body) else {
+        return Ok(());
+    };
+    let secret = generate_secret();
+    let callback = format!("{}/websub/{}", ctx.config.public_base_url.trim_end_matches('/'), feed_row.id);
+    let resp = reqwest::Client::new()
+        .post(&hub_url)
+        .form(&[
+            ("hub.mode", "subscribe"),
+            ("hub.topic", feed_row.feed_url.as_str()),
+            ("hub.callback", callback.as_str()),
+            ("hub.secret", secret.as_str()),
+        ])
+        .send().await.context("Failed to send WebSub subscription request")?;
+    if !resp.status().is_success() {
+        // The hub didn't accept the subscription: leave the feed on the polling schedule
+        // instead of writing a lease nothing is actually going to honor.
+        return Err(anyhow!("WebSub hub {} rejected subscription request with status {}", hub_url, resp.status()));
+    }
+    let lease_expires_at = Utc::now() + Duration::seconds(LEASE_SECONDS);
+    sqlx::query("INSERT INTO websub_subscriptions (feed_id, hub_url, topic_url, secret, lease_expires_at)\
+    VALUES ($1, $2, $3, $4, $5)\
+    ON CONFLICT(feed_id) DO UPDATE SET hub_url=excluded.hub_url, topic_url=excluded.topic_url,\
+        secret=excluded.secret, lease_expires_at=excluded.lease_expires_at")
+        .bind(feed_row.id).bind(&hub_url).bind(&feed_row.feed_url).bind(&secret).bind(lease_expires_at)
+        .execute(&ctx.db).await.context("Failed to persist WebSub subscription")?;
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect()
+}
+
+#[derive(Deserialize)]
+pub struct VerifyQuery {
+    #[serde(rename = "hub.challenge")]
+    challenge: Option<String>,
+}
+
+/// `GET /websub/:feed_id`: answers the hub's subscription-verification handshake.
+pub async fn verify_subscription(Query(query): Query<VerifyQuery>) -> impl IntoResponse {
+    match query.challenge {
+        Some(challenge) => (StatusCode::OK, challenge),
+        None => (StatusCode::BAD_REQUEST, String::new()),
+    }
+}
+
+/// `POST /websub/:feed_id`: the hub's push delivery. Rejects anything whose
+/// `X-Hub-Signature` HMAC-SHA256 doesn't match the stored per-subscription secret before
+/// parsing the payload and running it through the same new-item detection polling uses.
+pub async fn receive_update(
+    State(ctx): State<AppContext>,
+    Path(feed_id): Path<u32>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<StatusCode, StatusCode> {
+    let subscription: WebSubSubscription = sqlx::query_as(
+        "SELECT hub_url, secret FROM websub_subscriptions WHERE feed_id = $1")
+        .bind(feed_id)
+        .fetch_optional(&ctx.db).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let signature = headers.get("X-Hub-Signature").and_then(|h| h.to_str().ok()).ok_or(StatusCode::UNAUTHORIZED)?;
+    verify_signature(&subscription.secret, &body, signature).map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let rss_feed: RssFeed = sqlx::query_as("SELECT id, name, feed_url, last_pub_date FROM rss_feeds WHERE id = $1")
+        .bind(feed_id)
+        .fetch_optional(&ctx.db).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let items = feed::parse_feed(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    if let Err(e) = process_new_items(&ctx, &rss_feed, &items).await {
+        error!("Failed to process WebSub push for feed {}: {:#}", feed_id, e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    Ok(StatusCode::OK)
+}
+
+fn verify_signature(secret: &str, body: &[u8], header_value: &str) -> Result<()> {
+    let (algo, signature_hex) = header_value.split_once('=').ok_or(anyhow!("Malformed X-Hub-Signature header"))?;
+    if algo != "sha256" {
+        return Err(anyhow!("Unsupported signature algorithm {}", algo));
+    }
+    let expected = hex::decode(signature_hex).context("Malformed signature hex")?;
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).context("Invalid HMAC key")?;
+    mac.update(body);
+    mac.verify_slice(&expected).map_err(|_| anyhow!("Signature mismatch"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn accepts_a_valid_signature() {
+        let body = b"hello hub";
+        let header = sign("shared-secret", body);
+        assert!(verify_signature("shared-secret", body, &header).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_signature_from_the_wrong_secret() {
+        let body = b"hello hub";
+        let header = sign("wrong-secret", body);
+        assert!(verify_signature("shared-secret", body, &header).is_err());
+    }
+
+    #[test]
+    fn rejects_a_signature_for_different_body() {
+        let header = sign("shared-secret", b"hello hub");
+        assert!(verify_signature("shared-secret", b"tampered body", &header).is_err());
+    }
+
+    #[test]
+    fn rejects_a_malformed_header() {
+        let body = b"hello hub";
+        assert!(verify_signature("shared-secret", body, "not-a-valid-header").is_err());
+    }
+
+    #[test]
+    fn rejects_an_unsupported_algorithm() {
+        let body = b"hello hub";
+        assert!(verify_signature("shared-secret", body, "sha1=deadbeef").is_err());
+    }
+
+    #[test]
+    fn rejects_non_hex_signature() {
+        let body = b"hello hub";
+        assert!(verify_signature("shared-secret", body, "sha256=not-hex").is_err());
+    }
+}